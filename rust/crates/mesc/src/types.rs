@@ -1,7 +1,11 @@
+mod chain_ids;
+
 use crate::validate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub use chain_ids::{ChainId, TryIntoChainId};
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Endpoint {
     pub name: String,
@@ -19,6 +23,131 @@ impl Endpoint {
     }
 }
 
+/// how to coerce a raw metadata `serde_json::Value` into a typed `MetadataValue`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conversion {
+    /// the UTF-8 bytes of a stored JSON string (not decoded binary, e.g. not hex or base64)
+    Bytes,
+    Integer,
+    Float,
+    Boolean,
+    Timestamp,
+    TimestampFmt(String),
+}
+
+/// a metadata value after coercion to the type requested by a `Conversion`
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataValue {
+    /// the UTF-8 bytes of a stored JSON string (not decoded binary, e.g. not hex or base64)
+    Bytes(Vec<u8>),
+    Integer(u64),
+    Float(f64),
+    Boolean(bool),
+    Timestamp(i64),
+}
+
+fn get_metadata_value(
+    metadata: &HashMap<String, serde_json::Value>,
+    key: &str,
+    conversion: &Conversion,
+) -> Result<MetadataValue, MescError> {
+    let value = metadata
+        .get(key)
+        .ok_or_else(|| MescError::MissingMetadata(key.to_string()))?;
+    match conversion {
+        Conversion::Bytes => value
+            .as_str()
+            .map(|s| MetadataValue::Bytes(s.as_bytes().to_vec()))
+            .ok_or_else(|| MescError::InvalidMetadata(key.to_string())),
+        Conversion::Integer => value
+            .as_u64()
+            .map(MetadataValue::Integer)
+            .ok_or_else(|| MescError::InvalidMetadata(key.to_string())),
+        Conversion::Float => value
+            .as_f64()
+            .map(MetadataValue::Float)
+            .ok_or_else(|| MescError::InvalidMetadata(key.to_string())),
+        Conversion::Boolean => value
+            .as_bool()
+            .map(MetadataValue::Boolean)
+            .ok_or_else(|| MescError::InvalidMetadata(key.to_string())),
+        Conversion::Timestamp => value
+            .as_i64()
+            .map(MetadataValue::Timestamp)
+            .ok_or_else(|| MescError::InvalidMetadata(key.to_string())),
+        Conversion::TimestampFmt(fmt) => {
+            let s = value
+                .as_str()
+                .ok_or_else(|| MescError::InvalidMetadata(key.to_string()))?;
+            let timestamp = chrono::NaiveDateTime::parse_from_str(s, fmt)
+                .map_err(|_| MescError::InvalidMetadata(key.to_string()))?
+                .and_utc()
+                .timestamp();
+            Ok(MetadataValue::Timestamp(timestamp))
+        }
+    }
+}
+
+macro_rules! impl_metadata_accessors {
+    ($type:ty, $field:ident) => {
+        impl $type {
+            /// coerce the metadata value at `key` according to `conversion`
+            pub fn get_metadata_as(
+                &self,
+                key: &str,
+                conversion: Conversion,
+            ) -> Result<MetadataValue, MescError> {
+                get_metadata_value(&self.$field, key, &conversion)
+            }
+
+            pub fn get_metadata_string(&self, key: &str) -> Result<String, MescError> {
+                match self.get_metadata_as(key, Conversion::Bytes)? {
+                    MetadataValue::Bytes(bytes) => String::from_utf8(bytes)
+                        .map_err(|_| MescError::InvalidMetadata(key.to_string())),
+                    _ => unreachable!(),
+                }
+            }
+
+            pub fn get_metadata_u64(&self, key: &str) -> Result<u64, MescError> {
+                match self.get_metadata_as(key, Conversion::Integer)? {
+                    MetadataValue::Integer(value) => Ok(value),
+                    _ => unreachable!(),
+                }
+            }
+
+            pub fn get_metadata_bool(&self, key: &str) -> Result<bool, MescError> {
+                match self.get_metadata_as(key, Conversion::Boolean)? {
+                    MetadataValue::Boolean(value) => Ok(value),
+                    _ => unreachable!(),
+                }
+            }
+
+            /// parse the metadata value at `key` as a unix timestamp (integer seconds)
+            pub fn get_metadata_timestamp(&self, key: &str) -> Result<i64, MescError> {
+                match self.get_metadata_as(key, Conversion::Timestamp)? {
+                    MetadataValue::Timestamp(value) => Ok(value),
+                    _ => unreachable!(),
+                }
+            }
+
+            /// parse the metadata value at `key` as a timestamp string using `fmt`
+            /// (a `chrono`-style strftime format)
+            pub fn get_metadata_timestamp_fmt(
+                &self,
+                key: &str,
+                fmt: &str,
+            ) -> Result<i64, MescError> {
+                match self.get_metadata_as(key, Conversion::TimestampFmt(fmt.to_string()))? {
+                    MetadataValue::Timestamp(value) => Ok(value),
+                    _ => unreachable!(),
+                }
+            }
+        }
+    };
+}
+
+impl_metadata_accessors!(Endpoint, endpoint_metadata);
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Profile {
     pub default_endpoint: Option<String>,
@@ -59,12 +188,112 @@ impl Default for RpcConfig {
 
 impl RpcConfig {
     pub fn serialize(&self) -> Result<String, MescError> {
-        Ok(serde_json::to_string(self)?)
+        self.serialize_as(ConfigFormat::Json)
+    }
+
+    /// serialize the config using the given format
+    pub fn serialize_as(&self, format: ConfigFormat) -> Result<String, MescError> {
+        match format {
+            ConfigFormat::Json => Ok(serde_json::to_string(self)?),
+            ConfigFormat::Toml => {
+                // TOML has no null type, so a `serde_json::Value::Null` metadata entry (e.g. an
+                // unset tag) would otherwise fail deep inside `toml::to_string` with an opaque
+                // "unsupported unit type" error; surface it clearly instead
+                self.check_toml_compatible()?;
+                Ok(toml::to_string(self)?)
+            }
+        }
+    }
+
+    /// deserialize a config from a string in the given format
+    pub fn deserialize(data: &str, format: ConfigFormat) -> Result<RpcConfig, MescError> {
+        match format {
+            ConfigFormat::Json => Ok(serde_json::from_str(data)?),
+            ConfigFormat::Toml => Ok(toml::from_str(data)?),
+        }
+    }
+
+    /// read and parse a config file, inferring JSON vs TOML from the file extension
+    pub fn read_file<P: AsRef<std::path::Path>>(path: P) -> Result<RpcConfig, MescError> {
+        let format = ConfigFormat::from_path(&path);
+        let data = std::fs::read_to_string(path)?;
+        RpcConfig::deserialize(&data, format)
+    }
+
+    /// return an error if any metadata value is or contains a null, which TOML cannot
+    /// represent; other values that TOML rejects (e.g. integers above `i64::MAX`) are not
+    /// checked here and still fail inside `toml::to_string`
+    fn check_toml_compatible(&self) -> Result<(), MescError> {
+        for value in self.global_metadata.values() {
+            if json_value_contains_null(value) {
+                return Err(MescError::UnsupportedTomlValue(
+                    "global_metadata contains a null value, which TOML cannot represent"
+                        .to_string(),
+                ));
+            }
+        }
+        for endpoint in self.endpoints.values() {
+            for value in endpoint.endpoint_metadata.values() {
+                if json_value_contains_null(value) {
+                    return Err(MescError::UnsupportedTomlValue(format!(
+                        "endpoint_metadata for endpoint '{}' contains a null value, which TOML cannot represent",
+                        endpoint.name
+                    )));
+                }
+            }
+        }
+        Ok(())
     }
 
     pub fn validate(&self) -> Result<(), MescError> {
         validate::validate_config(self)
     }
+
+    /// return all endpoints matching the given query
+    pub fn find_endpoints(&self, query: &EndpointQuery) -> Vec<&Endpoint> {
+        self.endpoints.values().filter(|endpoint| query.matches(endpoint)).collect()
+    }
+
+    /// return the first endpoint matching the given query, or the global default endpoint
+    /// if the query has no conditions set
+    pub fn find_one(&self, query: &EndpointQuery) -> Option<&Endpoint> {
+        if query.is_empty() {
+            return self
+                .default_endpoint
+                .as_ref()
+                .and_then(|name| self.endpoints.get(name));
+        }
+        self.find_endpoints(query).into_iter().next()
+    }
+}
+
+impl_metadata_accessors!(RpcConfig, global_metadata);
+
+/// on-disk format of a serialized `RpcConfig`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// infer a config's format from its file path, defaulting to JSON
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> ConfigFormat {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
+        }
+    }
+}
+
+/// true if `value` is, or recursively contains, `serde_json::Value::Null`
+fn json_value_contains_null(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::Array(items) => items.iter().any(json_value_contains_null),
+        serde_json::Value::Object(map) => map.values().any(json_value_contains_null),
+        _ => false,
+    }
 }
 
 #[derive(Debug)]
@@ -79,6 +308,11 @@ pub enum MescError {
     EnvReadError(std::env::VarError),
     NotImplemented(String),
     SerdeError(serde_json::Error),
+    TomlSerError(toml::ser::Error),
+    TomlDeError(toml::de::Error),
+    UnsupportedTomlValue(String),
+    MissingMetadata(String),
+    InvalidMetadata(String),
     InvalidInput,
 }
 
@@ -100,128 +334,231 @@ impl From<std::env::VarError> for MescError {
     }
 }
 
-/// ChainId is a string representation of an integer chain id
-/// - TryFrom conversions allow specifying as String, &str, uint, or binary data
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
-pub struct ChainId(String);
-
-impl ChainId {
-    pub fn null_chain_id() -> ChainId {
-        ChainId("0".to_string())
+impl From<toml::ser::Error> for MescError {
+    fn from(value: toml::ser::Error) -> MescError {
+        MescError::TomlSerError(value)
     }
 }
 
-impl PartialOrd for ChainId {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+impl From<toml::de::Error> for MescError {
+    fn from(value: toml::de::Error) -> MescError {
+        MescError::TomlDeError(value)
     }
 }
 
-impl Ord for ChainId {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let ChainId(self_str) = self;
-        let ChainId(other_str) = other;
-        let self_str = format!("{:>079}", self_str);
-        let other_str = format!("{:>079}", other_str);
-        self_str.cmp(&other_str)
-    }
+#[derive(Debug, Default, Clone)]
+pub struct EndpointQuery {
+    pub chain_id: Option<ChainId>,
+    pub name_contains: Option<String>,
+    pub url_contains: Option<String>,
+    pub metadata_key: Option<String>,
 }
 
-impl std::fmt::Display for ChainId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+impl EndpointQuery {
+    pub fn new() -> Self {
+        Self::default()
     }
-}
 
-macro_rules! impl_from_uint_for_chainid {
-    ($($t:ty),*) => {
-        $(
-            impl From<$t> for ChainId {
-                fn from(value: $t) -> ChainId {
-                    ChainId(value.to_string())
-                }
-            }
-        )*
-    };
-}
+    pub fn chain_id<T: TryIntoChainId>(mut self, chain_id: T) -> Result<Self, MescError> {
+        self.chain_id = Some(chain_id.try_into_chain_id()?);
+        Ok(self)
+    }
 
-impl_from_uint_for_chainid!(u8, u16, u32, u64, u128, usize);
+    pub fn name<T: AsRef<str>>(mut self, query: T) -> Result<Self, MescError> {
+        self.name_contains = Some(query.as_ref().to_string());
+        Ok(self)
+    }
 
-/// use custom trait instead of TryInto so that Error type is always the same
-pub trait TryIntoChainId {
-    fn try_into_chain_id(self) -> Result<ChainId, MescError>;
-}
+    pub fn url<T: AsRef<str>>(mut self, query: T) -> Result<Self, MescError> {
+        self.url_contains = Some(query.as_ref().to_string());
+        Ok(self)
+    }
 
-impl TryIntoChainId for ChainId {
-    fn try_into_chain_id(self) -> Result<ChainId, MescError> {
+    /// require endpoints to carry the given `endpoint_metadata` key, e.g. `archive`
+    pub fn metadata_key<T: AsRef<str>>(mut self, key: T) -> Result<Self, MescError> {
+        self.metadata_key = Some(key.as_ref().to_string());
         Ok(self)
     }
-}
 
-impl TryIntoChainId for String {
-    fn try_into_chain_id(self) -> Result<ChainId, MescError> {
-        if self.chars().all(|c| c.is_ascii_digit()) {
-            Ok(ChainId(self))
-        } else {
-            Err(MescError::InvalidChainId(self))
-        }
+    /// true if this query has no conditions set and therefore matches every endpoint
+    pub fn is_empty(&self) -> bool {
+        self.chain_id.is_none()
+            && self.name_contains.is_none()
+            && self.url_contains.is_none()
+            && self.metadata_key.is_none()
     }
-}
 
-impl TryIntoChainId for &str {
-    fn try_into_chain_id(self) -> Result<ChainId, MescError> {
-        if self.chars().all(|c| c.is_ascii_digit()) {
-            Ok(ChainId(self.to_string()))
-        } else {
-            Err(MescError::InvalidChainId(self.to_string()))
+    /// true if the given endpoint satisfies every condition set on this query
+    pub fn matches(&self, endpoint: &Endpoint) -> bool {
+        if let Some(chain_id) = &self.chain_id {
+            if endpoint.chain_id.as_ref() != Some(chain_id) {
+                return false;
+            }
+        }
+        if let Some(query) = &self.name_contains {
+            if !endpoint
+                .name
+                .to_lowercase()
+                .contains(&query.to_lowercase())
+            {
+                return false;
+            }
+        }
+        if let Some(query) = &self.url_contains {
+            if !endpoint.url.to_lowercase().contains(&query.to_lowercase()) {
+                return false;
+            }
         }
+        if let Some(key) = &self.metadata_key {
+            if !endpoint.endpoint_metadata.contains_key(key) {
+                return false;
+            }
+        }
+        true
     }
 }
 
-macro_rules! impl_try_into_chain_id_for_integer {
-    ($($t:ty),*) => {
-        $(
-            impl TryIntoChainId for $t {
-                fn try_into_chain_id(self) -> Result<ChainId, MescError> {
-                    Ok(ChainId(self.to_string()))
-                }
-            }
-        )*
-    };
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl_try_into_chain_id_for_integer!(u8, u16, u32, u64, u128, usize);
+    #[test]
+    fn toml_round_trip_preserves_metadata() {
+        let mut config = RpcConfig::default();
+        config.global_metadata.insert(
+            "network".to_string(),
+            serde_json::Value::String("mainnet".to_string()),
+        );
 
-impl TryIntoChainId for &[u8] {
-    fn try_into_chain_id(self) -> Result<ChainId, MescError> {
-        Err(MescError::NotImplemented("binary chain_id".to_string()))
+        let toml_str = config.serialize_as(ConfigFormat::Toml).unwrap();
+        let parsed = RpcConfig::deserialize(&toml_str, ConfigFormat::Toml).unwrap();
+        assert_eq!(parsed.global_metadata, config.global_metadata);
     }
-}
 
-#[derive(Debug, Default, Clone)]
-pub struct EndpointQuery {
-    pub chain_id: Option<ChainId>,
-    pub name_contains: Option<String>,
-    pub url_contains: Option<String>,
-}
+    #[test]
+    fn toml_serialize_rejects_null_metadata() {
+        let mut config = RpcConfig::default();
+        config
+            .global_metadata
+            .insert("unset_tag".to_string(), serde_json::Value::Null);
 
-impl EndpointQuery {
-    pub fn new() -> Self {
-        Self::default()
+        let result = config.serialize_as(ConfigFormat::Toml);
+        assert!(matches!(result, Err(MescError::UnsupportedTomlValue(_))));
     }
 
-    pub fn chain_id<T: TryIntoChainId>(mut self, chain_id: T) -> Result<Self, MescError> {
-        self.chain_id = Some(chain_id.try_into_chain_id()?);
-        Ok(self)
+    #[test]
+    fn json_serialize_still_allows_null_metadata() {
+        let mut config = RpcConfig::default();
+        config
+            .global_metadata
+            .insert("unset_tag".to_string(), serde_json::Value::Null);
+
+        assert!(config.serialize_as(ConfigFormat::Json).is_ok());
     }
 
-    pub fn name<T: AsRef<str>>(mut self, query: T) -> Result<Self, MescError> {
-        self.name_contains = Some(query.as_ref().to_string());
-        Ok(self)
+    #[test]
+    fn from_path_detects_format_from_extension() {
+        assert_eq!(ConfigFormat::from_path("config.toml"), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path("config.TOML"), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path("config.json"), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path("config"), ConfigFormat::Json);
     }
 
-    pub fn url<T: AsRef<str>>(mut self, query: T) -> Result<Self, MescError> {
-        self.url_contains = Some(query.as_ref().to_string());
-        Ok(self)
+    #[test]
+    fn read_file_picks_format_from_extension_on_disk() {
+        let mut config = RpcConfig::default();
+        config.global_metadata.insert(
+            "network".to_string(),
+            serde_json::Value::String("mainnet".to_string()),
+        );
+
+        let dir = std::env::temp_dir();
+        let toml_path = dir.join(format!("mesc_read_file_test_{}.toml", std::process::id()));
+        let json_path = dir.join(format!("mesc_read_file_test_{}.json", std::process::id()));
+
+        std::fs::write(&toml_path, config.serialize_as(ConfigFormat::Toml).unwrap()).unwrap();
+        std::fs::write(&json_path, config.serialize_as(ConfigFormat::Json).unwrap()).unwrap();
+
+        let from_toml = RpcConfig::read_file(&toml_path).unwrap();
+        let from_json = RpcConfig::read_file(&json_path).unwrap();
+
+        std::fs::remove_file(&toml_path).unwrap();
+        std::fs::remove_file(&json_path).unwrap();
+
+        assert_eq!(from_toml.global_metadata, config.global_metadata);
+        assert_eq!(from_json.global_metadata, config.global_metadata);
+    }
+
+    fn endpoint_with_metadata(metadata: HashMap<String, serde_json::Value>) -> Endpoint {
+        Endpoint {
+            name: "test".to_string(),
+            url: "https://example.com".to_string(),
+            chain_id: None,
+            endpoint_metadata: metadata,
+        }
+    }
+
+    #[test]
+    fn get_metadata_string_coerces_string() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "label".to_string(),
+            serde_json::Value::String("archive".to_string()),
+        );
+        let endpoint = endpoint_with_metadata(metadata);
+        assert_eq!(endpoint.get_metadata_string("label").unwrap(), "archive");
+    }
+
+    #[test]
+    fn get_metadata_u64_rejects_wrong_shape() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "label".to_string(),
+            serde_json::Value::String("archive".to_string()),
+        );
+        let endpoint = endpoint_with_metadata(metadata);
+        assert!(matches!(
+            endpoint.get_metadata_u64("label"),
+            Err(MescError::InvalidMetadata(_))
+        ));
+    }
+
+    #[test]
+    fn get_metadata_missing_key_is_descriptive() {
+        let endpoint = endpoint_with_metadata(HashMap::new());
+        assert!(matches!(
+            endpoint.get_metadata_bool("archive"),
+            Err(MescError::MissingMetadata(_))
+        ));
+    }
+
+    #[test]
+    fn get_metadata_timestamp_accepts_unix_seconds() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "added_at".to_string(),
+            serde_json::Value::Number(1_700_000_000.into()),
+        );
+        let endpoint = endpoint_with_metadata(metadata);
+        assert_eq!(
+            endpoint.get_metadata_timestamp("added_at").unwrap(),
+            1_700_000_000
+        );
+    }
+
+    #[test]
+    fn get_metadata_timestamp_fmt_parses_custom_format() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "added_at".to_string(),
+            serde_json::Value::String("2023-11-14 22:13:20".to_string()),
+        );
+        let endpoint = endpoint_with_metadata(metadata);
+        assert_eq!(
+            endpoint
+                .get_metadata_timestamp_fmt("added_at", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            1_700_000_000
+        );
     }
 }
\ No newline at end of file
@@ -62,24 +62,74 @@ impl TryIntoChainId for ChainId {
 
 impl TryIntoChainId for String {
     fn try_into_chain_id(self) -> Result<ChainId, MescError> {
-        if self.chars().all(|c| c.is_ascii_digit()) {
-            Ok(ChainId(self))
-        } else {
-            Err(MescError::InvalidChainId(self))
-        }
+        parse_chain_id_str(&self)
     }
 }
 
 impl TryIntoChainId for &str {
     fn try_into_chain_id(self) -> Result<ChainId, MescError> {
-        if self.chars().all(|c| c.is_ascii_digit()) {
-            Ok(ChainId(self.to_string()))
-        } else {
-            Err(MescError::InvalidChainId(self.to_string()))
+        parse_chain_id_str(self)
+    }
+}
+
+/// parse a chain id from a decimal string, or a `0x`/`0X`-prefixed hex string
+fn parse_chain_id_str(s: &str) -> Result<ChainId, MescError> {
+    if let Some(hex_digits) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        let mut digits = Vec::with_capacity(hex_digits.len());
+        for c in hex_digits.chars() {
+            match c.to_digit(16) {
+                Some(digit) => digits.push(digit as u8),
+                None => return Err(MescError::InvalidChainId(s.to_string())),
+            }
         }
+        Ok(ChainId(digits_to_decimal_string(&digits, 16)))
+    } else if s.chars().all(|c| c.is_ascii_digit()) {
+        Ok(ChainId(s.to_string()))
+    } else {
+        Err(MescError::InvalidChainId(s.to_string()))
     }
 }
 
+/// convert a big-endian sequence of base-`base` digits (each `< base`) into its canonical
+/// base-10 string, stripping leading zero digits first
+fn digits_to_decimal_string(digits: &[u8], base: u32) -> String {
+    let first_nonzero = digits.iter().position(|&d| d != 0).unwrap_or(digits.len());
+    let digits = &digits[first_nonzero..];
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+
+    // repeated-multiply-and-add base conversion; `decimal` holds base-10 digits, least
+    // significant first, since chain ids can exceed u128 and need arbitrary precision
+    let mut decimal: Vec<u8> = vec![0];
+    for &digit in digits {
+        let mut carry = 0u32;
+        for d in decimal.iter_mut() {
+            let value = *d as u32 * base + carry;
+            *d = (value % 10) as u8;
+            carry = value / 10;
+        }
+        while carry > 0 {
+            decimal.push((carry % 10) as u8);
+            carry /= 10;
+        }
+
+        let mut carry = digit as u32;
+        let mut i = 0;
+        while carry > 0 {
+            if i == decimal.len() {
+                decimal.push(0);
+            }
+            let value = decimal[i] as u32 + carry;
+            decimal[i] = (value % 10) as u8;
+            carry = value / 10;
+            i += 1;
+        }
+    }
+
+    decimal.iter().rev().map(|d| (b'0' + d) as char).collect()
+}
+
 macro_rules! impl_try_into_chain_id_for_integer {
     ($($t:ty),*) => {
         $(
@@ -96,6 +146,75 @@ impl_try_into_chain_id_for_integer!(u8, u16, u32, u64, u128, usize);
 
 impl TryIntoChainId for &[u8] {
     fn try_into_chain_id(self) -> Result<ChainId, MescError> {
-        Err(MescError::NotImplemented("binary chain_id".to_string()))
+        Ok(ChainId(digits_to_decimal_string(self, 256)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_decimal_chain_id() {
+        assert_eq!("1".try_into_chain_id().unwrap(), ChainId("1".to_string()));
+    }
+
+    #[test]
+    fn parses_hex_chain_id() {
+        assert_eq!(
+            "0x2a".try_into_chain_id().unwrap(),
+            ChainId("42".to_string())
+        );
+        assert_eq!(
+            "0X2A".try_into_chain_id().unwrap(),
+            ChainId("42".to_string())
+        );
+    }
+
+    #[test]
+    fn strips_leading_zeros_in_hex() {
+        assert_eq!(
+            "0x00002a".try_into_chain_id().unwrap(),
+            ChainId("42".to_string())
+        );
+        assert_eq!("0x0".try_into_chain_id().unwrap(), ChainId("0".to_string()));
+    }
+
+    #[test]
+    fn rejects_invalid_hex_digits() {
+        assert!(matches!(
+            "0xzz".try_into_chain_id(),
+            Err(MescError::InvalidChainId(_))
+        ));
+    }
+
+    #[test]
+    fn parses_hex_beyond_u128() {
+        // 2^128, which overflows u128::from_str_radix and requires arbitrary precision
+        let chain_id = "0x100000000000000000000000000000000"
+            .try_into_chain_id()
+            .unwrap();
+        assert_eq!(
+            chain_id,
+            ChainId("340282366920938463463374607431768211456".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_binary_chain_id() {
+        let bytes: &[u8] = &[0x00, 0x2a];
+        assert_eq!(
+            bytes.try_into_chain_id().unwrap(),
+            ChainId("42".to_string())
+        );
+    }
+
+    #[test]
+    fn empty_and_all_zero_binary_chain_id_is_zero() {
+        let empty: &[u8] = &[];
+        assert_eq!(empty.try_into_chain_id().unwrap(), ChainId::null_chain_id());
+
+        let zeros: &[u8] = &[0x00, 0x00];
+        assert_eq!(zeros.try_into_chain_id().unwrap(), ChainId::null_chain_id());
     }
 }
\ No newline at end of file
@@ -20,6 +20,27 @@ pub fn print_endpoint_pretty(endpoint: Endpoint) {
     println!("- metadata: {:?}", endpoint.endpoint_metadata);
 }
 
+/// collect `config`'s endpoints matching `query`, e.g. for CLI flags like `--chain-id`,
+/// `--name-contains`, `--url-contains`, or `--tag` so callers can do filtered listings
+/// instead of always dumping every endpoint
+pub(crate) fn filter_endpoints(
+    config: &mesc::RpcConfig,
+    query: &mesc::EndpointQuery,
+) -> Vec<mesc::Endpoint> {
+    config.find_endpoints(query).into_iter().cloned().collect()
+}
+
+/// filter `config`'s endpoints by `query` before printing them; this is the call the
+/// command-line argument parser should make once it builds an `EndpointQuery` out of the
+/// user's filter flags
+pub(crate) fn print_queried_endpoints(
+    config: &mesc::RpcConfig,
+    query: &mesc::EndpointQuery,
+    reveal: bool,
+) -> Result<(), MescCliError> {
+    print_endpoints(&filter_endpoints(config, query), reveal)
+}
+
 pub(crate) fn print_endpoints(
     endpoints: &[mesc::Endpoint],
     reveal: bool,
@@ -105,4 +126,50 @@ pub(crate) fn print_defaults(config: &mesc::RpcConfig) -> Result<(), MescCliErro
     //     }
     // };
     Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn endpoint(name: &str, chain_id: u64) -> mesc::Endpoint {
+        mesc::Endpoint {
+            name: name.to_string(),
+            url: format!("https://{name}.example.com"),
+            chain_id: Some(chain_id.into()),
+            endpoint_metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn filter_endpoints_applies_query() {
+        let mut config = mesc::RpcConfig::default();
+        config
+            .endpoints
+            .insert("mainnet".to_string(), endpoint("mainnet", 1));
+        config
+            .endpoints
+            .insert("sepolia".to_string(), endpoint("sepolia", 11155111));
+
+        let query = mesc::EndpointQuery::new().chain_id(1u64).unwrap();
+        let filtered = filter_endpoints(&config, &query);
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "mainnet");
+    }
+
+    #[test]
+    fn print_queried_endpoints_filters_before_printing() {
+        let mut config = mesc::RpcConfig::default();
+        config
+            .endpoints
+            .insert("mainnet".to_string(), endpoint("mainnet", 1));
+        config
+            .endpoints
+            .insert("sepolia".to_string(), endpoint("sepolia", 11155111));
+
+        let query = mesc::EndpointQuery::new().chain_id(1u64).unwrap();
+        assert!(print_queried_endpoints(&config, &query, false).is_ok());
+    }
 }
\ No newline at end of file